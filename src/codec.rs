@@ -0,0 +1,102 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use serde_json::Value;
+
+use crate::{Error, Message};
+
+/// A pluggable wire encoding for `Message`.
+pub trait Codec {
+    fn encode(message: &Message) -> Vec<u8>;
+    fn decode(bytes: &[u8]) -> Result<Message, Error>;
+}
+
+/// The crate's default codec: plain JSON text.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json;
+
+impl Codec for Json {
+    fn encode(message: &Message) -> Vec<u8> {
+        let value = Value::from(message.clone());
+
+        serde_json::to_vec(&value).unwrap_or_default()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Message, Error> {
+        let value: Value =
+            serde_json::from_slice(bytes).map_err(|e| Error::ParseError(e.to_string()))?;
+
+        Message::try_from(&value)
+    }
+}
+
+/// A MessagePack codec, for peers that negotiate a binary encoding.
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MsgPack;
+
+#[cfg(feature = "msgpack")]
+impl Codec for MsgPack {
+    fn encode(message: &Message) -> Vec<u8> {
+        let value = Value::from(message.clone());
+
+        rmp_serde::to_vec(&value).unwrap_or_default()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Message, Error> {
+        let value: Value =
+            rmp_serde::from_slice(bytes).map_err(|e| Error::ParseError(e.to_string()))?;
+
+        Message::try_from(&value)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use crate::{Notification, Params};
+
+    use super::*;
+
+    #[test]
+    fn json_round_trips_a_message() {
+        let message = Message::Notification(Notification {
+            method: "a".to_string(),
+            params: Params::Null,
+        });
+
+        let bytes = Json::encode(&message);
+
+        assert_eq!(Json::decode(&bytes), Ok(message));
+    }
+
+    #[test]
+    fn json_syntax_error_is_a_parse_error_not_unsupported_format() {
+        let err = Json::decode(b"{not valid json").unwrap_err();
+
+        assert!(matches!(err, Error::ParseError(_)));
+        assert_eq!(err.to_rpc_error().code, -32700);
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn msgpack_round_trips_a_message() {
+        let message = Message::Notification(Notification {
+            method: "a".to_string(),
+            params: Params::Null,
+        });
+
+        let bytes = MsgPack::encode(&message);
+
+        assert_eq!(MsgPack::decode(&bytes), Ok(message));
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn msgpack_syntax_error_is_a_parse_error_not_unsupported_format() {
+        let err = MsgPack::decode(&[0xc1]).unwrap_err();
+
+        assert!(matches!(err, Error::ParseError(_)));
+        assert_eq!(err.to_rpc_error().code, -32700);
+    }
+}
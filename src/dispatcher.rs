@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use serde_json::{json, Value};
+
+use crate::{Batch, BatchEntry, Notification, Params, Request, Response, RpcError};
+
+type HandlerFuture = Pin<Box<dyn Future<Output = Result<Value, RpcError>> + Send>>;
+type Handler = Arc<dyn Fn(Params) -> HandlerFuture + Send + Sync>;
+
+/// Routes incoming `Request`/`Notification` values to registered handlers by
+/// method name.
+#[derive(Clone, Default)]
+pub struct Dispatcher {
+    handlers: HashMap<String, Handler>,
+}
+
+impl Dispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an async handler for `method`.
+    pub fn register<M, F, Fut>(&mut self, method: M, handler: F) -> &mut Self
+    where
+        M: Into<String>,
+        F: Fn(Params) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Value, RpcError>> + Send + 'static,
+    {
+        self.handlers.insert(
+            method.into(),
+            Arc::new(move |params| Box::pin(handler(params))),
+        );
+
+        self
+    }
+
+    /// Dispatches a `Request`, always producing a `Response`.
+    pub async fn dispatch_request(&self, request: Request) -> Response {
+        let Request { id, method, params } = request;
+
+        let result = match self.handlers.get(&method) {
+            Some(handler) => handler(params).await,
+            None => Err(RpcError::method_not_found(Some(Value::String(method)))),
+        };
+
+        Response { id, result }
+    }
+
+    /// Dispatches a `Notification`, discarding any result.
+    pub async fn dispatch_notification(&self, notification: Notification) {
+        let Notification { method, params } = notification;
+
+        if let Some(handler) = self.handlers.get(&method) {
+            handler(params).await.ok();
+        }
+    }
+
+    /// Dispatches every entry of a `Batch`: requests contribute a
+    /// `Response`, notifications run for effect only, and entries that
+    /// failed to parse become an `Invalid Request` error with a null id.
+    /// Returns `None` when there is nothing to send back, e.g. when every
+    /// member of the batch was a notification.
+    pub async fn dispatch_batch(&self, batch: Batch) -> Option<Value> {
+        let mut values = Vec::new();
+
+        for entry in batch.0 {
+            match entry {
+                Ok(BatchEntry::Request(request)) => {
+                    values.push(Value::from(self.dispatch_request(request).await));
+                }
+
+                Ok(BatchEntry::Notification(notification)) => {
+                    self.dispatch_notification(notification).await;
+                }
+
+                Err(e) => values.push(json!({
+                    "jsonrpc": "2.0",
+                    "id": Value::Null,
+                    "error": Value::from(e.to_rpc_error()),
+                })),
+            }
+        }
+
+        (!values.is_empty()).then(|| Value::Array(values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use crate::Id;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn unknown_method_is_method_not_found() {
+        let dispatcher = Dispatcher::new();
+
+        let response = dispatcher
+            .dispatch_request(Request {
+                id: Id::Number(1),
+                method: "missing".to_string(),
+                params: Params::Null,
+            })
+            .await;
+
+        assert_eq!(response.id, Id::Number(1));
+        assert_eq!(
+            response.result,
+            Err(RpcError::method_not_found(Some(json!("missing"))))
+        );
+    }
+
+    #[tokio::test]
+    async fn registered_handler_runs_on_the_happy_path() {
+        let mut dispatcher = Dispatcher::new();
+
+        dispatcher.register("add", |params: Params| async move {
+            let (a, b): (i64, i64) = params.parse().map_err(|_| RpcError::invalid_params(None))?;
+
+            Ok(json!(a + b))
+        });
+
+        let response = dispatcher
+            .dispatch_request(Request {
+                id: Id::Number(1),
+                method: "add".to_string(),
+                params: Params::Array(vec![json!(2), json!(3)]),
+            })
+            .await;
+
+        assert_eq!(response.result, Ok(json!(5)));
+    }
+
+    #[tokio::test]
+    async fn notification_result_is_discarded() {
+        let called = Arc::new(AtomicBool::new(false));
+        let called_thr = Arc::clone(&called);
+
+        let mut dispatcher = Dispatcher::new();
+
+        dispatcher.register("ping", move |_params: Params| {
+            let called = Arc::clone(&called_thr);
+
+            async move {
+                called.store(true, Ordering::SeqCst);
+
+                Err(RpcError::internal_error(None))
+            }
+        });
+
+        dispatcher
+            .dispatch_notification(Notification {
+                method: "ping".to_string(),
+                params: Params::Null,
+            })
+            .await;
+
+        assert!(called.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn all_notification_batch_sends_nothing_back() {
+        let mut dispatcher = Dispatcher::new();
+
+        dispatcher.register("ping", |_params: Params| async { Ok(Value::Null) });
+
+        let batch = Batch(vec![
+            Ok(BatchEntry::Notification(Notification {
+                method: "ping".to_string(),
+                params: Params::Null,
+            })),
+            Ok(BatchEntry::Notification(Notification {
+                method: "ping".to_string(),
+                params: Params::Null,
+            })),
+        ]);
+
+        assert_eq!(dispatcher.dispatch_batch(batch).await, None);
+    }
+
+    #[tokio::test]
+    async fn batch_mixes_responses_and_malformed_entry_errors() {
+        let mut dispatcher = Dispatcher::new();
+
+        dispatcher.register("ping", |_params: Params| async { Ok(json!("pong")) });
+
+        let batch = Batch(vec![
+            Ok(BatchEntry::Request(Request {
+                id: Id::Number(1),
+                method: "ping".to_string(),
+                params: Params::Null,
+            })),
+            Err(crate::Error::ExpectedMethod),
+        ]);
+
+        let value = dispatcher.dispatch_batch(batch).await.unwrap();
+        let values = value.as_array().unwrap();
+
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0]["result"], json!("pong"));
+        assert_eq!(values[1]["id"], Value::Null);
+        assert_eq!(values[1]["error"]["code"], json!(-32600));
+    }
+}
@@ -107,6 +107,15 @@ impl Params {
             _ => None,
         }
     }
+
+    /// Deserializes the params into a strongly-typed `T`, e.g. a struct for
+    /// `Params::Object` or a tuple for `Params::Array`.
+    pub fn parse<T>(&self) -> Result<T, Error>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        serde_json::from_value(Value::from(self)).map_err(|_| Error::InvalidParams)
+    }
 }
 
 impl From<Params> for Value {
@@ -291,6 +300,99 @@ impl TryFrom<&Value> for Notification {
     }
 }
 
+impl RpcError {
+    pub fn parse_error(data: Option<Value>) -> Self {
+        Self {
+            code: -32700,
+            message: String::from("Parse error"),
+            data: data.unwrap_or(Value::Null),
+        }
+    }
+
+    pub fn invalid_request(data: Option<Value>) -> Self {
+        Self {
+            code: -32600,
+            message: String::from("Invalid Request"),
+            data: data.unwrap_or(Value::Null),
+        }
+    }
+
+    pub fn method_not_found(data: Option<Value>) -> Self {
+        Self {
+            code: -32601,
+            message: String::from("Method not found"),
+            data: data.unwrap_or(Value::Null),
+        }
+    }
+
+    pub fn invalid_params(data: Option<Value>) -> Self {
+        Self {
+            code: -32602,
+            message: String::from("Invalid params"),
+            data: data.unwrap_or(Value::Null),
+        }
+    }
+
+    pub fn internal_error(data: Option<Value>) -> Self {
+        Self {
+            code: -32603,
+            message: String::from("Internal error"),
+            data: data.unwrap_or(Value::Null),
+        }
+    }
+
+    /// Builds an implementation-defined server error. `code` must fall
+    /// within the reserved `-32099..=-32000` range.
+    pub fn server_error(
+        code: i64,
+        message: impl Into<String>,
+        data: Option<Value>,
+    ) -> Result<Self, Error> {
+        if !(-32099..=-32000).contains(&code) {
+            return Err(Error::InvalidServerErrorCode);
+        }
+
+        Ok(Self {
+            code,
+            message: message.into(),
+            data: data.unwrap_or(Value::Null),
+        })
+    }
+}
+
+impl Error {
+    /// Maps a wire-parsing failure onto the closest standard JSON-RPC error,
+    /// so it can be surfaced directly in a `Response`.
+    pub fn to_rpc_error(&self) -> RpcError {
+        match self {
+            Error::ParseError(_) => RpcError::parse_error(None),
+            Error::InvalidMethodVariant => RpcError::method_not_found(None),
+            Error::ExpectedMethod => RpcError::invalid_request(None),
+            Error::InvalidParams => RpcError::invalid_params(None),
+            Error::InvalidServerErrorCode => RpcError::internal_error(None),
+            Error::UnexpectedIdVariant
+            | Error::UnexpectedParamsVariant
+            | Error::UnexpectedRequestVariant
+            | Error::InvalidNumberCast
+            | Error::JsonRpcVersionNotFound
+            | Error::InvalidJsonRpcVersion
+            | Error::ExpectedId
+            | Error::UnexpectedNotificationVariant
+            | Error::UnexpectedErrorVariant
+            | Error::ExpectedErrorCode
+            | Error::ExpectedErrorCodeAsInteger
+            | Error::ExpectedErrorMessage
+            | Error::ExpectedErrorCodeAsString
+            | Error::UnexpectedResponseVariant
+            | Error::ResponseExpectsResultOrError
+            | Error::EmptyBatch
+            | Error::UnexpectedBatchEntryVariant
+            | Error::UnexpectedMessageVariant
+            | Error::UnsupportedFormat => RpcError::invalid_request(None),
+        }
+    }
+}
+
 impl fmt::Display for RpcError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.message.fmt(f)
@@ -510,3 +612,188 @@ impl<'de> Deserialize<'de> for Response {
         Value::deserialize(deserializer).and_then(|v| Self::try_from(v).map_err(D::Error::custom))
     }
 }
+
+impl From<BatchEntry> for Value {
+    fn from(entry: BatchEntry) -> Self {
+        match entry {
+            BatchEntry::Request(r) => r.into(),
+            BatchEntry::Notification(n) => n.into(),
+        }
+    }
+}
+
+impl TryFrom<&Value> for BatchEntry {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        Request::try_from(value)
+            .map(Self::Request)
+            .or_else(|_| Notification::try_from(value).map(Self::Notification))
+            .map_err(|_| Error::UnexpectedBatchEntryVariant)
+    }
+}
+
+impl From<Batch> for Value {
+    fn from(batch: Batch) -> Self {
+        Value::Array(
+            batch
+                .0
+                .into_iter()
+                .map(|entry| match entry {
+                    Ok(entry) => Value::from(entry),
+                    Err(e) => json!({
+                        "jsonrpc": "2.0",
+                        "id": Value::Null,
+                        "error": Value::from(e.to_rpc_error()),
+                    }),
+                })
+                .collect(),
+        )
+    }
+}
+
+impl TryFrom<&Value> for Batch {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let array = value.as_array().ok_or(Error::UnexpectedBatchEntryVariant)?;
+
+        if array.is_empty() {
+            return Err(Error::EmptyBatch);
+        }
+
+        Ok(Self(array.iter().map(BatchEntry::try_from).collect()))
+    }
+}
+
+impl From<Message> for Value {
+    fn from(message: Message) -> Self {
+        match message {
+            Message::Request(r) => r.into(),
+            Message::Notification(n) => n.into(),
+            Message::Response(r) => r.into(),
+            Message::Batch(b) => b.into(),
+        }
+    }
+}
+
+impl TryFrom<&Value> for Message {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        if value.is_array() {
+            return Batch::try_from(value).map(Self::Batch);
+        }
+
+        let map = value.as_object().ok_or(Error::UnexpectedMessageVariant)?;
+
+        if map.contains_key("result") || map.contains_key("error") {
+            return Response::try_from(value).map(Self::Response);
+        }
+
+        if map.contains_key("method") {
+            return if map.contains_key("id") {
+                Request::try_from(value).map(Self::Request)
+            } else {
+                Notification::try_from(value).map(Self::Notification)
+            };
+        }
+
+        Err(Error::UnexpectedMessageVariant)
+    }
+}
+
+impl<'de> Deserialize<'de> for Message {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Value::deserialize(deserializer).and_then(|v| Self::try_from(&v).map_err(D::Error::custom))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_batch_is_rejected() {
+        let value = json!([]);
+
+        assert_eq!(Batch::try_from(&value), Err(Error::EmptyBatch));
+    }
+
+    #[test]
+    fn batch_of_valid_entries_parses() {
+        let value = json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "a"},
+            {"jsonrpc": "2.0", "method": "b"},
+        ]);
+
+        let batch = Batch::try_from(&value).unwrap();
+
+        assert!(batch.0.iter().all(Result::is_ok));
+        assert_eq!(batch.0.len(), 2);
+    }
+
+    #[test]
+    fn a_malformed_entry_does_not_discard_the_rest_of_the_batch() {
+        let value = json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "a"},
+            {"jsonrpc": "2.0"},
+            {"jsonrpc": "2.0", "method": "b"},
+        ]);
+
+        let batch = Batch::try_from(&value).unwrap();
+
+        assert_eq!(batch.0.len(), 3);
+        assert!(batch.0[0].is_ok());
+        assert_eq!(batch.0[1], Err(Error::ExpectedMethod));
+        assert!(batch.0[2].is_ok());
+    }
+
+    #[test]
+    fn message_demuxes_a_request_by_the_presence_of_id_and_method() {
+        let value = json!({"jsonrpc": "2.0", "id": 1, "method": "a"});
+
+        assert!(matches!(Message::try_from(&value), Ok(Message::Request(_))));
+    }
+
+    #[test]
+    fn message_demuxes_a_notification_by_method_without_id() {
+        let value = json!({"jsonrpc": "2.0", "method": "a"});
+
+        assert!(matches!(
+            Message::try_from(&value),
+            Ok(Message::Notification(_))
+        ));
+    }
+
+    #[test]
+    fn message_demuxes_a_response_by_result_or_error() {
+        let value = json!({"jsonrpc": "2.0", "id": 1, "result": 1});
+
+        assert!(matches!(
+            Message::try_from(&value),
+            Ok(Message::Response(_))
+        ));
+    }
+
+    #[test]
+    fn message_demuxes_a_top_level_array_as_a_batch() {
+        let value = json!([{"jsonrpc": "2.0", "method": "a"}]);
+
+        assert!(matches!(Message::try_from(&value), Ok(Message::Batch(_))));
+    }
+
+    #[test]
+    fn message_rejects_an_object_with_neither_method_nor_result_nor_error() {
+        let value = json!({"jsonrpc": "2.0"});
+
+        assert_eq!(
+            Message::try_from(&value),
+            Err(Error::UnexpectedMessageVariant)
+        );
+    }
+}
@@ -2,19 +2,37 @@
 
 extern crate alloc;
 
+mod codec;
 mod impls;
 
 #[cfg(feature = "reactor")]
 mod reactor;
 
+#[cfg(feature = "transport")]
+mod transport;
+
+#[cfg(feature = "dispatcher")]
+mod dispatcher;
+
 use alloc::string::String;
 use alloc::vec::Vec;
 
 use serde::{Deserialize, Serialize};
 
+pub use codec::{Codec, Json};
+
+#[cfg(feature = "msgpack")]
+pub use codec::MsgPack;
+
 #[cfg(feature = "reactor")]
 pub use reactor::Reactor;
 
+#[cfg(feature = "transport")]
+pub use transport::{TransportError, TransportReader, TransportWriter};
+
+#[cfg(feature = "dispatcher")]
+pub use dispatcher::Dispatcher;
+
 pub use serde_json::{json, Map, Value};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -36,6 +54,13 @@ pub enum Error {
     ExpectedErrorCodeAsString,
     UnexpectedResponseVariant,
     ResponseExpectsResultOrError,
+    EmptyBatch,
+    UnexpectedBatchEntryVariant,
+    InvalidParams,
+    InvalidServerErrorCode,
+    UnexpectedMessageVariant,
+    UnsupportedFormat,
+    ParseError(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -76,3 +101,27 @@ pub struct Response {
     pub id: Id,
     pub result: Result<Value, RpcError>,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchEntry {
+    Request(Request),
+    Notification(Notification),
+}
+
+/// Entries that failed to parse are kept as `Err` rather than discarding
+/// the whole array.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Batch(pub Vec<Result<BatchEntry, Error>>);
+
+/// An incoming frame whose shape hasn't been decided yet: reading from a
+/// peer, you don't know up front whether the next value is a call, a
+/// notification, a reply, or a batch of those. `Message` demultiplexes a
+/// decoded `Value` into the right variant so a transport loop can route it
+/// without guessing first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Request(Request),
+    Notification(Notification),
+    Response(Response),
+    Batch(Batch),
+}
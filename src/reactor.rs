@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::future::Future;
 use std::sync::Arc;
 use std::time;
 
@@ -11,8 +13,34 @@ use crate::{Id, Notification, Params, Request, Response, RpcError};
 #[derive(Debug)]
 struct PendingRequest {
     sender: oneshot::Sender<Result<Value, RpcError>>,
+}
+
+/// Ordered solely by `moment`, so a min-heap of these always surfaces the
+/// soonest expiry first.
+#[derive(Debug)]
+struct Deadline {
     moment: time::Instant,
-    timeout: Option<time::Duration>,
+    id: Id,
+}
+
+impl PartialEq for Deadline {
+    fn eq(&self, other: &Self) -> bool {
+        self.moment == other.moment
+    }
+}
+
+impl Eq for Deadline {}
+
+impl PartialOrd for Deadline {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Deadline {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.moment.cmp(&other.moment)
+    }
 }
 
 #[derive(Debug)]
@@ -22,6 +50,8 @@ pub struct Reactor {
     requests: mpsc::Sender<Request>,
     notifications: mpsc::Sender<Notification>,
     pending: Arc<sync::RwLock<HashMap<Id, PendingRequest>>>,
+    deadlines: Arc<sync::Mutex<BinaryHeap<Reverse<Deadline>>>>,
+    reaper: Arc<sync::Notify>,
 }
 
 impl Reactor {
@@ -38,6 +68,9 @@ impl Reactor {
         let pending = Arc::new(pending);
         let pending_thr = Arc::clone(&pending);
 
+        let deadlines = Arc::new(sync::Mutex::new(BinaryHeap::new()));
+        let reaper = Arc::new(sync::Notify::new());
+
         tokio::spawn(async move {
             while let Some(Response { id, result }) = responses.recv().await {
                 let mut pending = pending_thr.write().await;
@@ -48,17 +81,80 @@ impl Reactor {
             }
         });
 
+        Self::spawn_reaper(
+            Arc::clone(&pending),
+            Arc::clone(&deadlines),
+            Arc::clone(&reaper),
+        );
+
         let slf = Self {
             capacity,
             request_id,
             requests,
             notifications,
             pending,
+            deadlines,
+            reaper,
         };
 
         (slf, responses_tx)
     }
 
+    /// Sleeps until the nearest deadline (or until `reaper` is notified of a
+    /// new, possibly sooner, one), then evicts that pending request and
+    /// fires its timeout error.
+    fn spawn_reaper(
+        pending: Arc<sync::RwLock<HashMap<Id, PendingRequest>>>,
+        deadlines: Arc<sync::Mutex<BinaryHeap<Reverse<Deadline>>>>,
+        reaper: Arc<sync::Notify>,
+    ) {
+        tokio::spawn(async move {
+            loop {
+                let next = deadlines.lock().await.peek().map(|Reverse(d)| d.moment);
+
+                match next {
+                    Some(moment) => {
+                        let now = time::Instant::now();
+
+                        if moment > now {
+                            tokio::select! {
+                                _ = tokio::time::sleep(moment - now) => {}
+                                _ = reaper.notified() => continue,
+                            }
+                        }
+
+                        let expired = {
+                            let mut deadlines = deadlines.lock().await;
+
+                            match deadlines.peek() {
+                                Some(Reverse(d)) if d.moment <= time::Instant::now() => {
+                                    deadlines.pop().map(|Reverse(d)| d)
+                                }
+                                _ => None,
+                            }
+                        };
+
+                        let Some(expired) = expired else { continue };
+
+                        let mut pending = pending.write().await;
+
+                        if let Some(PendingRequest { sender }) = pending.remove(&expired.id) {
+                            let response = Err(RpcError {
+                                code: -1,
+                                message: String::from("response timeout"),
+                                data: Value::Null,
+                            });
+
+                            sender.send(response).ok();
+                        }
+                    }
+
+                    None => reaper.notified().await,
+                }
+            }
+        });
+    }
+
     pub async fn notify<M, P>(
         &mut self,
         method: M,
@@ -131,45 +227,153 @@ impl Reactor {
         }
 
         let (sender, receiver) = oneshot::channel();
-        let pending = PendingRequest {
-            sender,
-            moment: time::Instant::now(),
-            timeout,
-        };
+        let pending = PendingRequest { sender };
 
         let mut queue = self.pending.write().await;
 
-        queue.insert(id, pending);
-
-        // attempt to clean expired pending responses
-        if self.capacity < queue.len() {
-            let now = time::Instant::now();
-
-            let expired = queue
-                .iter()
-                .filter_map(|(id, pending)| {
-                    pending.timeout.and_then(|t| {
-                        let diff = now.duration_since(pending.moment);
-
-                        (t < diff).then_some(id)
-                    })
-                })
-                .cloned()
-                .collect::<Vec<_>>();
-
-            for id in expired {
-                if let Some(pending) = queue.remove(&id) {
-                    let response = Err(RpcError {
-                        code: -1,
-                        message: String::from("response timeout"),
-                        data: Value::Null,
-                    });
-
-                    pending.sender.send(response).ok();
+        queue.insert(id.clone(), pending);
+
+        drop(queue);
+
+        if let Some(t) = timeout {
+            let deadline = Deadline {
+                moment: time::Instant::now() + t,
+                id,
+            };
+
+            self.deadlines.lock().await.push(Reverse(deadline));
+            self.reaper.notify_one();
+        }
+
+        Some(receiver)
+    }
+
+    /// Issues several correlated requests as a batch and returns a future
+    /// that resolves once every matching response has arrived, in the same
+    /// order the calls were given. Returns `None` if any of the calls could
+    /// not be sent, rolling back the ids already registered for the calls
+    /// that did succeed, from both `pending` and `deadlines`, so none of
+    /// them are left orphaned.
+    pub async fn batch<M, P>(
+        &mut self,
+        calls: Vec<(M, P)>,
+        timeout: Option<time::Duration>,
+    ) -> Option<impl Future<Output = Vec<Result<Value, RpcError>>>>
+    where
+        M: AsRef<str>,
+        P: Into<Params>,
+    {
+        let mut receivers = Vec::with_capacity(calls.len());
+        let mut sent_ids = Vec::with_capacity(calls.len());
+
+        for (method, params) in calls {
+            let id = Id::Number(self.request_id);
+
+            self.request_id = self.request_id.wrapping_add(1);
+
+            let Some(receiver) = self
+                .request_with_id(id.clone(), method, params, timeout)
+                .await
+            else {
+                let mut pending = self.pending.write().await;
+
+                for id in &sent_ids {
+                    pending.remove(id);
+                }
+
+                drop(pending);
+
+                if timeout.is_some() {
+                    let mut deadlines = self.deadlines.lock().await;
+
+                    *deadlines = deadlines
+                        .drain()
+                        .filter(|Reverse(d)| !sent_ids.contains(&d.id))
+                        .collect();
                 }
+
+                return None;
+            };
+
+            sent_ids.push(id);
+            receivers.push(receiver);
+        }
+
+        Some(async move {
+            let mut results = Vec::with_capacity(receivers.len());
+
+            for receiver in receivers {
+                let result = receiver
+                    .await
+                    .unwrap_or_else(|_| Err(RpcError::internal_error(None)));
+
+                results.push(result);
             }
+
+            results
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reaper_fires_in_deadline_order_not_registration_order() {
+        let (requests_tx, requests_rx) = mpsc::channel(8);
+        let (notifications_tx, notifications_rx) = mpsc::channel(8);
+        let (mut reactor, _responses_tx) = Reactor::spawn(8, requests_tx, notifications_tx);
+
+        let long = reactor
+            .request_with_id(
+                Id::Number(1),
+                "a",
+                Vec::<Value>::new(),
+                Some(time::Duration::from_millis(200)),
+            )
+            .await
+            .unwrap();
+
+        let short = reactor
+            .request_with_id(
+                Id::Number(2),
+                "b",
+                Vec::<Value>::new(),
+                Some(time::Duration::from_millis(20)),
+            )
+            .await
+            .unwrap();
+
+        tokio::select! {
+            _ = short => {}
+            _ = long => panic!("longer-timeout request fired before the shorter one"),
         }
 
-        Some(receiver)
+        drop(requests_rx);
+        drop(notifications_rx);
+    }
+
+    #[tokio::test]
+    async fn batch_rollback_clears_deadlines_as_well_as_pending() {
+        // A capacity-1 channel with nothing draining it: the first call's
+        // send fills it, so the second call's `send_timeout` can never
+        // succeed and `batch` has to roll back.
+        let (requests_tx, requests_rx) = mpsc::channel(1);
+        let (notifications_tx, notifications_rx) = mpsc::channel(1);
+        let (mut reactor, _responses_tx) = Reactor::spawn(8, requests_tx, notifications_tx);
+
+        let calls = vec![("a", Vec::<Value>::new()), ("b", Vec::<Value>::new())];
+
+        let batch = reactor
+            .batch(calls, Some(time::Duration::from_millis(20)))
+            .await;
+
+        assert!(batch.is_none());
+        assert!(reactor.pending.read().await.is_empty());
+        assert!(reactor.deadlines.lock().await.is_empty());
+
+        drop(requests_rx);
+        drop(notifications_rx);
     }
 }
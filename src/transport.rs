@@ -0,0 +1,280 @@
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+
+use tokio::io::{
+    AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader, BufWriter,
+};
+
+use crate::codec::Json;
+use crate::{Codec, Error, Message};
+
+/// Default ceiling on the `Content-Length` header, guarding against a
+/// misbehaving peer claiming an unreasonably large body.
+const DEFAULT_MAX_CONTENT_LENGTH: usize = 32 * 1024 * 1024;
+
+#[derive(Debug)]
+pub enum TransportError {
+    Io(io::Error),
+    Decode(Error),
+    MissingContentLength,
+    InvalidContentLength,
+    ContentLengthTooLarge(usize),
+    UnexpectedEof,
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "transport io error: {e}"),
+            Self::Decode(e) => write!(f, "transport decode error: {e}"),
+            Self::MissingContentLength => write!(f, "missing Content-Length header"),
+            Self::InvalidContentLength => write!(f, "non-numeric Content-Length header"),
+            Self::ContentLengthTooLarge(n) => {
+                write!(f, "Content-Length {n} exceeds the configured maximum")
+            }
+            Self::UnexpectedEof => write!(f, "connection closed mid-frame"),
+        }
+    }
+}
+
+impl std::error::Error for TransportError {}
+
+impl From<io::Error> for TransportError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<Error> for TransportError {
+    fn from(e: Error) -> Self {
+        Self::Decode(e)
+    }
+}
+
+/// Reads `Content-Length`-framed messages off an [`AsyncRead`].
+#[derive(Debug)]
+pub struct TransportReader<R, C = Json> {
+    inner: BufReader<R>,
+    max_content_length: usize,
+    codec: PhantomData<C>,
+}
+
+impl<R, C> TransportReader<R, C>
+where
+    R: AsyncRead + Unpin,
+    C: Codec,
+{
+    pub fn new(inner: R) -> Self {
+        Self::with_max_content_length(inner, DEFAULT_MAX_CONTENT_LENGTH)
+    }
+
+    pub fn with_max_content_length(inner: R, max_content_length: usize) -> Self {
+        Self {
+            inner: BufReader::new(inner),
+            max_content_length,
+            codec: PhantomData,
+        }
+    }
+
+    /// Reads and decodes the next framed message, or `None` on a clean EOF
+    /// between frames. A peer that disconnects mid-frame is an error, not
+    /// a clean shutdown.
+    pub async fn read_message(&mut self) -> Result<Option<Message>, TransportError> {
+        let Some(body) = self.read_frame().await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(C::decode(&body)?))
+    }
+
+    async fn read_frame(&mut self) -> Result<Option<Vec<u8>>, TransportError> {
+        let mut content_length = None;
+        let mut line = String::new();
+        let mut header_lines_read = 0usize;
+
+        loop {
+            line.clear();
+
+            if self.inner.read_line(&mut line).await? == 0 {
+                return if header_lines_read == 0 {
+                    Ok(None)
+                } else {
+                    Err(TransportError::UnexpectedEof)
+                };
+            }
+
+            header_lines_read += 1;
+
+            let line = line.trim_end_matches(['\r', '\n']);
+
+            if line.is_empty() {
+                break;
+            }
+
+            if let Some(value) = line
+                .split_once(':')
+                .filter(|(name, _)| name.eq_ignore_ascii_case("Content-Length"))
+                .map(|(_, value)| value.trim())
+            {
+                let value = value
+                    .parse::<usize>()
+                    .map_err(|_| TransportError::InvalidContentLength)?;
+
+                content_length = Some(value);
+            }
+        }
+
+        let content_length = content_length.ok_or(TransportError::MissingContentLength)?;
+
+        if content_length > self.max_content_length {
+            return Err(TransportError::ContentLengthTooLarge(content_length));
+        }
+
+        let mut body = vec![0u8; content_length];
+
+        self.inner.read_exact(&mut body).await?;
+
+        Ok(Some(body))
+    }
+}
+
+/// Writes `Content-Length`-framed messages to an [`AsyncWrite`].
+#[derive(Debug)]
+pub struct TransportWriter<W, C = Json> {
+    inner: BufWriter<W>,
+    codec: PhantomData<C>,
+}
+
+impl<W, C> TransportWriter<W, C>
+where
+    W: AsyncWrite + Unpin,
+    C: Codec,
+{
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: BufWriter::new(inner),
+            codec: PhantomData,
+        }
+    }
+
+    pub async fn write_message(&mut self, message: &Message) -> Result<(), TransportError> {
+        let body = C::encode(message);
+
+        self.inner
+            .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+            .await?;
+        self.inner.write_all(&body).await?;
+        self.inner.flush().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Notification, Params};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn round_trips_multiple_frames() {
+        let (client, server) = tokio::io::duplex(4096);
+
+        let first = Message::Notification(Notification {
+            method: "a".to_string(),
+            params: Params::Null,
+        });
+        let second = Message::Notification(Notification {
+            method: "b".to_string(),
+            params: Params::Null,
+        });
+
+        let mut writer = TransportWriter::<_, Json>::new(client);
+
+        writer.write_message(&first).await.unwrap();
+        writer.write_message(&second).await.unwrap();
+
+        let mut reader = TransportReader::<_, Json>::new(server);
+
+        assert_eq!(reader.read_message().await.unwrap(), Some(first));
+        assert_eq!(reader.read_message().await.unwrap(), Some(second));
+    }
+
+    #[tokio::test]
+    async fn missing_content_length_is_an_error() {
+        let (mut client, server) = tokio::io::duplex(4096);
+
+        client.write_all(b"X-Custom: 1\r\n\r\n").await.unwrap();
+        drop(client);
+
+        let mut reader = TransportReader::<_, Json>::new(server);
+
+        assert!(matches!(
+            reader.read_message().await,
+            Err(TransportError::MissingContentLength)
+        ));
+    }
+
+    #[tokio::test]
+    async fn non_numeric_content_length_is_an_error() {
+        let (mut client, server) = tokio::io::duplex(4096);
+
+        client
+            .write_all(b"Content-Length: not-a-number\r\n\r\n")
+            .await
+            .unwrap();
+        drop(client);
+
+        let mut reader = TransportReader::<_, Json>::new(server);
+
+        assert!(matches!(
+            reader.read_message().await,
+            Err(TransportError::InvalidContentLength)
+        ));
+    }
+
+    #[tokio::test]
+    async fn oversized_content_length_is_an_error() {
+        let (mut client, server) = tokio::io::duplex(4096);
+
+        client
+            .write_all(b"Content-Length: 1000\r\n\r\n")
+            .await
+            .unwrap();
+        drop(client);
+
+        let mut reader = TransportReader::<_, Json>::with_max_content_length(server, 10);
+
+        assert!(matches!(
+            reader.read_message().await,
+            Err(TransportError::ContentLengthTooLarge(1000))
+        ));
+    }
+
+    #[tokio::test]
+    async fn disconnect_mid_headers_is_unexpected_eof_not_a_clean_shutdown() {
+        let (mut client, server) = tokio::io::duplex(4096);
+
+        client.write_all(b"Content-Length: 10\r\n").await.unwrap();
+        drop(client);
+
+        let mut reader = TransportReader::<_, Json>::new(server);
+
+        assert!(matches!(
+            reader.read_message().await,
+            Err(TransportError::UnexpectedEof)
+        ));
+    }
+
+    #[tokio::test]
+    async fn clean_eof_between_frames_is_not_an_error() {
+        let (client, server) = tokio::io::duplex(4096);
+
+        drop(client);
+
+        let mut reader = TransportReader::<_, Json>::new(server);
+
+        assert!(matches!(reader.read_message().await, Ok(None)));
+    }
+}